@@ -0,0 +1,3 @@
+//! Base types shared across the `geo` modules.
+
+pub mod types;
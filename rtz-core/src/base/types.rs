@@ -0,0 +1,4 @@
+//! Shared numeric type aliases.
+
+/// The floating-point type used for coordinates and geometry throughout this crate.
+pub type Float = f64;
@@ -0,0 +1,3 @@
+//! Geo-specific lookups.
+
+pub mod tz;
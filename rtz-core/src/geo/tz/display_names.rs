@@ -0,0 +1,292 @@
+//! Localized display names for resolved timezones, built from CLDR's `timeZoneNames.json`.
+//!
+//! Resolving a point to a [`NedTimezone`](crate::geo::tz::ned::NedTimezone) only gets you an
+//! IANA identifier like `America/Los_Angeles`.  This module turns that into the
+//! human-readable, locale-specific names CLDR ships for UIs: the long/short generic,
+//! standard, and daylight forms (e.g. "Pacific Time", "PST", "Pacific Daylight Time"), and
+//! the exemplar city ("Los Angeles").
+
+use std::collections::HashMap;
+#[cfg(feature = "self-contained")]
+use std::{path::Path, sync::OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+/// The name of the localized display name bincode file.
+pub static DISPLAY_NAMES_BINCODE_DESTINATION_NAME: &str = "tz_display_names.bincode";
+
+/// Which CLDR timezone name form to resolve with
+/// [`NedTimezone::display_name`](crate::geo::tz::ned::NedTimezone::display_name).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// The long generic form (e.g. "Pacific Time").
+    LongGeneric,
+    /// The long standard form (e.g. "Pacific Standard Time").
+    LongStandard,
+    /// The long daylight form (e.g. "Pacific Daylight Time").
+    LongDaylight,
+    /// The short generic form (e.g. "PT").
+    ShortGeneric,
+    /// The short standard form (e.g. "PST").
+    ShortStandard,
+    /// The short daylight form (e.g. "PDT").
+    ShortDaylight,
+    /// The exemplar city (e.g. "Los Angeles").
+    ExemplarCity,
+}
+
+/// The flattened, per-(locale, zone) names resolved at build time.
+///
+/// Generic/standard/daylight forms are resolved from the zone's own `timeZoneNames` entry
+/// when CLDR has one, falling back to its metazone's forms otherwise; at lookup time there
+/// is nothing left to fall back across except the exemplar city.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ZoneNames {
+    exemplar_city: Option<String>,
+    long_generic: Option<String>,
+    long_standard: Option<String>,
+    long_daylight: Option<String>,
+    short_generic: Option<String>,
+    short_standard: Option<String>,
+    short_daylight: Option<String>,
+}
+
+impl ZoneNames {
+    fn form(&self, style: Style) -> Option<&str> {
+        match style {
+            Style::LongGeneric => self.long_generic.as_deref(),
+            Style::LongStandard => self.long_standard.as_deref(),
+            Style::LongDaylight => self.long_daylight.as_deref(),
+            Style::ShortGeneric => self.short_generic.as_deref(),
+            Style::ShortStandard => self.short_standard.as_deref(),
+            Style::ShortDaylight => self.short_daylight.as_deref(),
+            Style::ExemplarCity => self.exemplar_city.as_deref(),
+        }
+    }
+}
+
+/// Generate the bincode representation of the localized display names from a directory of
+/// CLDR `timeZoneNames.json` files (one per locale, e.g. `main/en/timeZoneNames.json`) plus
+/// the supplemental `metaZones.json` that maps each IANA zone to its metazone.
+///
+/// For every locale, the zone's own `exemplarCity`/`long`/`short` entries win when present;
+/// any `long`/`short` form CLDR leaves unspecified is backfilled from the zone's metazone
+/// entry before being flattened into the bincode, since that's the only fallback CLDR
+/// defines beyond the exemplar-city format string `{0}`.
+///
+/// Called by a downstream crate's own `build.rs`, which then `include_bytes!`s the result
+/// and hands it to [`init_display_names`] at startup; see that function for why `rtz-core`
+/// doesn't do this itself.
+#[cfg(feature = "self-contained")]
+pub fn generate_display_names_bincode(cldr_timezone_names_dir: impl AsRef<Path>, cldr_metazones_json: impl AsRef<Path>, bincode_destination: impl AsRef<Path>) {
+    let metazone_of: HashMap<String, String> = {
+        let contents = std::fs::read_to_string(cldr_metazones_json).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+
+        json["supplemental"]["metaZones"]["metazoneInfo"]["timezone"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .filter_map(|(zone, mappings)| {
+                let metazone = mappings.as_array()?.last()?["usesMetazone"]["_mzone"].as_str()?;
+                Some((zone.clone(), metazone.to_owned()))
+            })
+            .collect()
+    };
+
+    let mut table: HashMap<(String, String), ZoneNames> = HashMap::new();
+
+    for entry in std::fs::read_dir(cldr_timezone_names_dir).unwrap() {
+        let path = entry.unwrap().path();
+        let Some(locale) = path.file_stem().and_then(|name| name.to_str()).map(ToOwned::to_owned) else {
+            continue;
+        };
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        let time_zone_names = &json["main"][&locale]["dates"]["timeZoneNames"];
+
+        let mut zones = HashMap::new();
+        flatten_zone_object(&time_zone_names["zone"], String::new(), &mut zones);
+
+        let metazones = time_zone_names["metazone"].as_object().cloned().unwrap_or_default();
+
+        let zone_ids: std::collections::HashSet<&String> = zones.keys().chain(metazone_of.keys()).collect();
+
+        for zone in zone_ids {
+            let mut names = zones.get(zone).map(zone_names_from_node).unwrap_or_default();
+
+            if let Some(metazone) = metazone_of.get(zone) {
+                if let Some(metazone_node) = metazones.get(metazone) {
+                    let fallback = zone_names_from_node(metazone_node);
+                    backfill(&mut names, fallback);
+                }
+            }
+
+            table.insert((locale.clone(), zone.clone()), names);
+        }
+    }
+
+    std::fs::write(bincode_destination, bincode::serde::encode_to_vec(&table, bincode::config::standard()).unwrap()).unwrap();
+}
+
+/// Recursively walk CLDR's nested `zone` object (keyed by IANA path segment, e.g.
+/// `zone.America.Los_Angeles`) and collect each leaf's full `Area/Location` identifier.
+#[cfg_attr(not(feature = "self-contained"), allow(dead_code))]
+fn flatten_zone_object(node: &serde_json::Value, prefix: String, out: &mut HashMap<String, serde_json::Value>) {
+    let Some(object) = node.as_object() else {
+        return;
+    };
+
+    if object.contains_key("exemplarCity") || object.contains_key("long") || object.contains_key("short") {
+        out.insert(prefix, node.clone());
+        return;
+    }
+
+    for (key, child) in object {
+        let child_prefix = if prefix.is_empty() { key.clone() } else { format!("{prefix}/{key}") };
+        flatten_zone_object(child, child_prefix, out);
+    }
+}
+
+#[cfg_attr(not(feature = "self-contained"), allow(dead_code))]
+fn zone_names_from_node(node: &serde_json::Value) -> ZoneNames {
+    let text = |path: &[&str]| -> Option<String> {
+        let mut value = node;
+        for segment in path {
+            value = value.get(segment)?;
+        }
+        value.as_str().map(ToOwned::to_owned)
+    };
+
+    ZoneNames {
+        exemplar_city: text(&["exemplarCity"]),
+        long_generic: text(&["long", "generic"]),
+        long_standard: text(&["long", "standard"]),
+        long_daylight: text(&["long", "daylight"]),
+        short_generic: text(&["short", "generic"]),
+        short_standard: text(&["short", "standard"]),
+        short_daylight: text(&["short", "daylight"]),
+    }
+}
+
+#[cfg_attr(not(feature = "self-contained"), allow(dead_code))]
+fn backfill(names: &mut ZoneNames, fallback: ZoneNames) {
+    names.long_generic = names.long_generic.take().or(fallback.long_generic);
+    names.long_standard = names.long_standard.take().or(fallback.long_standard);
+    names.long_daylight = names.long_daylight.take().or(fallback.long_daylight);
+    names.short_generic = names.short_generic.take().or(fallback.short_generic);
+    names.short_standard = names.short_standard.take().or(fallback.short_standard);
+    names.short_daylight = names.short_daylight.take().or(fallback.short_daylight);
+}
+
+#[cfg(feature = "self-contained")]
+static DISPLAY_NAMES: OnceLock<HashMap<(String, String), ZoneNames>> = OnceLock::new();
+
+/// Seed the localized display name table from a decoded [`generate_display_names_bincode`]
+/// blob.
+///
+/// `rtz-core` has no `build.rs` of its own, so it can't embed this data itself via
+/// `include_bytes!(concat!(env!("OUT_DIR"), ...))` -- that only resolves for a crate with its
+/// own build script. A downstream crate's build script generates and embeds the bincode, then
+/// calls this once at startup so [`resolve`] has something to read. A later call is a no-op.
+#[cfg(feature = "self-contained")]
+pub fn init_display_names(bincode_bytes: &[u8]) {
+    let (table, _len): (HashMap<(String, String), ZoneNames>, usize) = bincode::serde::decode_from_slice(bincode_bytes, bincode::config::standard()).unwrap();
+    let _ = DISPLAY_NAMES.set(table);
+}
+
+#[cfg(feature = "self-contained")]
+fn display_names(locale: &str, zone: &str) -> Option<&'static ZoneNames> {
+    DISPLAY_NAMES.get()?.get(&(locale.to_owned(), zone.to_owned()))
+}
+
+#[cfg(not(feature = "self-contained"))]
+fn display_names(_locale: &str, _zone: &str) -> Option<&'static ZoneNames> {
+    None
+}
+
+/// Resolve the localized display name for `zone` under `locale` in the requested `style`.
+///
+/// Falls back to the exemplar city (CLDR's `{0}` fallback format) when the specific
+/// generic/standard/daylight form isn't available; returns `None` when neither is known,
+/// e.g. for an unrecognized locale, when built without the `self-contained` feature, or
+/// before [`init_display_names`] has been called.
+pub(crate) fn resolve(locale: &str, zone: &str, style: Style) -> Option<String> {
+    let names = display_names(locale, zone)?;
+
+    if style == Style::ExemplarCity {
+        return names.exemplar_city.clone();
+    }
+
+    names.form(style).or(names.exemplar_city.as_deref()).map(ToOwned::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flatten_zone_object_collects_leaf_entries_by_path() {
+        let zone: serde_json::Value = serde_json::json!({
+            "America": {
+                "Los_Angeles": { "exemplarCity": "Los Angeles" },
+            },
+            "Pacific": {
+                "Kiritimati": { "long": { "generic": "Line Islands Time" } },
+            },
+        });
+
+        let mut out = HashMap::new();
+        flatten_zone_object(&zone, String::new(), &mut out);
+
+        assert_eq!(out.len(), 2);
+        assert!(out.contains_key("America/Los_Angeles"));
+        assert!(out.contains_key("Pacific/Kiritimati"));
+    }
+
+    #[test]
+    fn backfill_only_fills_in_forms_the_zone_itself_left_unspecified() {
+        let mut names = ZoneNames {
+            long_generic: Some("Pacific Time".to_owned()),
+            ..Default::default()
+        };
+        let fallback = ZoneNames {
+            long_generic: Some("Should not be used".to_owned()),
+            long_standard: Some("Pacific Standard Time".to_owned()),
+            ..Default::default()
+        };
+
+        backfill(&mut names, fallback);
+
+        assert_eq!(names.long_generic.as_deref(), Some("Pacific Time"));
+        assert_eq!(names.long_standard.as_deref(), Some("Pacific Standard Time"));
+        assert_eq!(names.long_daylight, None);
+    }
+
+    /// Exercises the whole `self-contained` path a downstream `build.rs` is responsible for:
+    /// a display-name table encoded to bincode (as [`generate_display_names_bincode`] would
+    /// write it), handed to [`init_display_names`] (as a downstream crate's `include_bytes!`
+    /// would), and then read back through [`resolve`], including its fallback from a
+    /// specific style to the exemplar city.
+    #[test]
+    #[cfg(feature = "self-contained")]
+    fn generated_bincode_round_trips_through_init_and_resolve() {
+        let mut table = HashMap::new();
+        table.insert(
+            ("en".to_owned(), "America/Los_Angeles".to_owned()),
+            ZoneNames {
+                exemplar_city: Some("Los Angeles".to_owned()),
+                long_generic: Some("Pacific Time".to_owned()),
+                ..Default::default()
+            },
+        );
+        let bytes = bincode::serde::encode_to_vec(&table, bincode::config::standard()).unwrap();
+
+        init_display_names(&bytes);
+
+        assert_eq!(resolve("en", "America/Los_Angeles", Style::LongGeneric).as_deref(), Some("Pacific Time"));
+        assert_eq!(resolve("en", "America/Los_Angeles", Style::LongStandard).as_deref(), Some("Los Angeles"));
+        assert_eq!(resolve("en", "Unknown/Zone", Style::ExemplarCity), None);
+    }
+}
@@ -0,0 +1,5 @@
+//! Natural Earth timezone types and the lookups built on top of them.
+
+pub mod display_names;
+pub mod names;
+pub mod ned;
@@ -0,0 +1,194 @@
+//! IANA &harr; BCP47 timezone identifier mapping.
+//!
+//! Consumers that interoperate with CLDR/ICU tooling generally want the short BCP47 Unicode
+//! time zone IDs (e.g. `uslax`) rather than the long IANA strings stored on
+//! [`NedTimezone::identifier`](crate::geo::tz::ned::NedTimezone::identifier).  This module
+//! builds that mapping from CLDR's `bcp47/timezone.xml` alias table.
+
+use std::collections::HashMap;
+#[cfg(feature = "self-contained")]
+use std::{path::Path, sync::OnceLock};
+
+#[cfg(feature = "self-contained")]
+use serde::{Deserialize, Serialize};
+
+/// The name of the BCP47 name mapping bincode file.
+pub static BCP47_NAMES_BINCODE_DESTINATION_NAME: &str = "bcp47_names.bincode";
+
+/// The IANA &harr; BCP47 mapping tables, generated from CLDR's `bcp47/timezone.xml`.
+#[cfg(feature = "self-contained")]
+#[derive(Debug, Serialize, Deserialize)]
+struct Bcp47Names {
+    /// Lowercased IANA identifier (including deprecated aliases) to BCP47 id.
+    iana_to_bcp47: HashMap<String, String>,
+    /// BCP47 id to canonical IANA identifier.
+    bcp47_to_iana: HashMap<String, String>,
+}
+
+/// Record one CLDR `<type name="{bcp47_id}" alias="{aliases}" .../>` entry into the two
+/// mapping tables.
+///
+/// `aliases` is CLDR's space-separated IANA alias list, the *last* entry of which is the
+/// canonical spelling and any earlier ones are deprecated aliases (e.g. `"Asia/Calcutta
+/// Asia/Kolkata"`: `Asia/Calcutta` is deprecated, `Asia/Kolkata` is canonical).  Every
+/// alias, canonical or deprecated, maps to the same BCP47 id, so two spellings of the same
+/// zone collapse to one short code.
+///
+/// Pulled out of [`generate_bcp47_names_bincode`] so the alias-ordering rule above has unit
+/// coverage without needing the `self-contained` feature's CLDR input files.
+#[cfg_attr(not(feature = "self-contained"), allow(dead_code))]
+fn insert_bcp47_mapping(bcp47_id: &str, aliases: &str, iana_to_bcp47: &mut HashMap<String, String>, bcp47_to_iana: &mut HashMap<String, String>) {
+    let aliases: Vec<&str> = aliases.split_whitespace().collect();
+    let Some(canonical) = aliases.last() else {
+        return;
+    };
+
+    for alias in &aliases {
+        iana_to_bcp47.insert(alias.to_lowercase(), bcp47_id.to_owned());
+    }
+
+    bcp47_to_iana.insert(bcp47_id.to_owned(), (*canonical).to_owned());
+}
+
+/// Generate the bincode representation of the IANA &harr; BCP47 mapping from CLDR's
+/// `bcp47/timezone.xml`.
+///
+/// Called by a downstream crate's own `build.rs`, which then `include_bytes!`s the result
+/// and hands it to [`init_bcp47_names`] at startup; see that function for why `rtz-core`
+/// doesn't do this itself.
+#[cfg(feature = "self-contained")]
+pub fn generate_bcp47_names_bincode(cldr_bcp47_timezone_xml: impl AsRef<Path>, bincode_destination: impl AsRef<Path>) {
+    let xml = std::fs::read_to_string(cldr_bcp47_timezone_xml).unwrap();
+    let document = roxmltree::Document::parse(&xml).unwrap();
+
+    let mut iana_to_bcp47 = HashMap::new();
+    let mut bcp47_to_iana = HashMap::new();
+
+    for type_node in document.descendants().filter(|node| node.has_tag_name("type")) {
+        let (Some(bcp47_id), Some(aliases)) = (type_node.attribute("name"), type_node.attribute("alias")) else {
+            continue;
+        };
+
+        insert_bcp47_mapping(bcp47_id, aliases, &mut iana_to_bcp47, &mut bcp47_to_iana);
+    }
+
+    let names = Bcp47Names { iana_to_bcp47, bcp47_to_iana };
+
+    std::fs::write(bincode_destination, bincode::serde::encode_to_vec(&names, bincode::config::standard()).unwrap()).unwrap();
+}
+
+#[cfg(feature = "self-contained")]
+static BCP47_NAMES: OnceLock<Bcp47Names> = OnceLock::new();
+
+/// Seed the IANA &harr; BCP47 mapping tables from a decoded [`generate_bcp47_names_bincode`]
+/// blob.
+///
+/// `rtz-core` has no `build.rs` of its own, so it can't embed this data itself via
+/// `include_bytes!(concat!(env!("OUT_DIR"), ...))` -- that only resolves for a crate with its
+/// own build script. A downstream crate's build script generates and embeds the bincode, then
+/// calls this once at startup so [`iana_to_bcp47`]/[`bcp47_to_iana`] have something to read. A
+/// later call is a no-op.
+#[cfg(feature = "self-contained")]
+pub fn init_bcp47_names(bincode_bytes: &[u8]) {
+    let (names, _len): (Bcp47Names, usize) = bincode::serde::decode_from_slice(bincode_bytes, bincode::config::standard()).unwrap();
+    let _ = BCP47_NAMES.set(names);
+}
+
+#[cfg(feature = "self-contained")]
+fn bcp47_names() -> Option<&'static Bcp47Names> {
+    BCP47_NAMES.get()
+}
+
+/// Map an IANA timezone identifier (e.g. `America/Los_Angeles`) to its canonical short
+/// BCP47 Unicode time zone ID (e.g. `uslax`).
+///
+/// The lookup is case-insensitive, and deprecated IANA aliases (e.g. `Asia/Calcutta`)
+/// resolve to the same id as their canonical replacement (`Asia/Kolkata`). Returns `None`
+/// before [`init_bcp47_names`] has been called.
+#[cfg(feature = "self-contained")]
+pub fn iana_to_bcp47(iana: &str) -> Option<&'static str> {
+    bcp47_names()?.iana_to_bcp47.get(&iana.to_lowercase()).map(String::as_str)
+}
+
+/// Map an IANA timezone identifier to its canonical short BCP47 Unicode time zone ID.
+///
+/// Always returns `None`; built without the `self-contained` feature, which embeds the
+/// CLDR-derived mapping tables.
+#[cfg(not(feature = "self-contained"))]
+pub fn iana_to_bcp47(_iana: &str) -> Option<&'static str> {
+    None
+}
+
+/// Map a short BCP47 Unicode time zone ID (e.g. `uslax`) back to its canonical IANA
+/// timezone identifier (e.g. `America/Los_Angeles`). Returns `None` before
+/// [`init_bcp47_names`] has been called.
+#[cfg(feature = "self-contained")]
+pub fn bcp47_to_iana(bcp47: &str) -> Option<&'static str> {
+    bcp47_names()?.bcp47_to_iana.get(bcp47).map(String::as_str)
+}
+
+/// Map a short BCP47 Unicode time zone ID back to its canonical IANA timezone identifier.
+///
+/// Always returns `None`; built without the `self-contained` feature, which embeds the
+/// CLDR-derived mapping tables.
+#[cfg(not(feature = "self-contained"))]
+pub fn bcp47_to_iana(_bcp47: &str) -> Option<&'static str> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_iana_id_is_the_last_alias_not_the_first() {
+        let mut iana_to_bcp47 = HashMap::new();
+        let mut bcp47_to_iana = HashMap::new();
+
+        insert_bcp47_mapping("inccu", "Asia/Calcutta Asia/Kolkata", &mut iana_to_bcp47, &mut bcp47_to_iana);
+
+        assert_eq!(bcp47_to_iana.get("inccu").map(String::as_str), Some("Asia/Kolkata"));
+    }
+
+    #[test]
+    fn deprecated_and_canonical_aliases_both_resolve_to_the_same_bcp47_id() {
+        let mut iana_to_bcp47 = HashMap::new();
+        let mut bcp47_to_iana = HashMap::new();
+
+        insert_bcp47_mapping("inccu", "Asia/Calcutta Asia/Kolkata", &mut iana_to_bcp47, &mut bcp47_to_iana);
+
+        assert_eq!(iana_to_bcp47.get("asia/calcutta").map(String::as_str), Some("inccu"));
+        assert_eq!(iana_to_bcp47.get("asia/kolkata").map(String::as_str), Some("inccu"));
+    }
+
+    #[test]
+    fn a_single_alias_is_its_own_canonical_id() {
+        let mut iana_to_bcp47 = HashMap::new();
+        let mut bcp47_to_iana = HashMap::new();
+
+        insert_bcp47_mapping("uslax", "America/Los_Angeles", &mut iana_to_bcp47, &mut bcp47_to_iana);
+
+        assert_eq!(bcp47_to_iana.get("uslax").map(String::as_str), Some("America/Los_Angeles"));
+    }
+
+    /// Exercises the whole `self-contained` path a downstream `build.rs` is responsible for:
+    /// a [`Bcp47Names`] table encoded to bincode (as [`generate_bcp47_names_bincode`] would
+    /// write it), handed to [`init_bcp47_names`] (as a downstream crate's `include_bytes!`
+    /// would), and then read back through the public lookup functions.
+    #[test]
+    #[cfg(feature = "self-contained")]
+    fn generated_bincode_round_trips_through_init_and_lookup() {
+        let mut iana_map = HashMap::new();
+        let mut bcp47_map = HashMap::new();
+        insert_bcp47_mapping("inccu", "Asia/Calcutta Asia/Kolkata", &mut iana_map, &mut bcp47_map);
+
+        let names = Bcp47Names { iana_to_bcp47: iana_map, bcp47_to_iana: bcp47_map };
+        let bytes = bincode::serde::encode_to_vec(&names, bincode::config::standard()).unwrap();
+
+        init_bcp47_names(&bytes);
+
+        assert_eq!(iana_to_bcp47("Asia/Kolkata"), Some("inccu"));
+        assert_eq!(iana_to_bcp47("Asia/Calcutta"), Some("inccu"));
+        assert_eq!(bcp47_to_iana("inccu"), Some("Asia/Kolkata"));
+    }
+}
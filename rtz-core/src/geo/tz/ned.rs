@@ -1,36 +1,110 @@
 //! All of the geo-specific functions for NED TZ lookups.
 
 use std::{collections::HashMap, ops::Deref, path::Path};
+#[cfg(feature = "self-contained")]
+use std::sync::OnceLock;
 
 use chashmap::CHashMap;
+use chrono::{DateTime, FixedOffset, Utc};
 use geo::{Coord, Geometry, Intersects, Rect};
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use rstar::{RTree, RTreeObject, AABB};
 use serde::{Deserialize, Serialize};
 
 use geojson::{FeatureCollection, GeoJson};
 
-use crate::base::types::Float;
+use crate::{
+    base::types::Float,
+    geo::tz::{display_names, display_names::Style, names},
+};
+
+/// Target number of intersecting zones a quadtree cell is allowed before
+/// [`get_cache_from_timezones`] recursively splits it into four sub-cells.
+const TARGET_CELL_OCCUPANCY: usize = 5;
+
+/// The deepest a quadtree cell is allowed to subdivide, i.e. the finest cell size is
+/// `1° / 2^MAX_QUADTREE_DEPTH`.
+const MAX_QUADTREE_DEPTH: u8 = 6;
+
+/// A zone's `bbox`, indexed in an `rstar` R-tree purely so quadtree cell generation can ask
+/// "which zones could possibly intersect this cell?" in roughly `O(log zones)` instead of
+/// scanning every zone in the dataset.
+struct ZoneBbox {
+    id: usize,
+    bbox: Rect<Float>,
+}
 
-/// Get the cache from the timezones.
-pub fn get_cache_from_timezones(timezones: &ConcreteTimezones) -> HashMap<RoundLngLat, Vec<i16>> {
-    let map = CHashMap::new();
+impl RTreeObject for ZoneBbox {
+    type Envelope = AABB<[Float; 2]>;
 
-    (-180..180).into_par_iter().for_each(|x| {
-        for y in -90..90 {
-            let xf = x as Float;
-            let yf = y as Float;
+    fn envelope(&self) -> Self::Envelope {
+        AABB::from_corners([self.bbox.min().x, self.bbox.min().y], [self.bbox.max().x, self.bbox.max().y])
+    }
+}
 
-            let rect = Rect::new(Coord { x: xf, y: yf }, Coord { x: xf + 1.0, y: yf + 1.0 });
+/// The key of a quadtree cell: its column and row at `level`, plus `level` itself.
+///
+/// At `level`, the globe is divided into `360 * 2^level` columns and `180 * 2^level` rows
+/// of equal-angle cells; `(column, row)` are the grid indices of one such cell, not a raw
+/// longitude/latitude, so the key stays an exact integer at every depth.
+pub type QuadtreeCellKey = (i32, i32, u8);
+
+/// The grid column/row of the cell whose lower-left corner is `(x, y)` at `level`.
+fn cell_origin_key(x: Float, y: Float, level: u8) -> QuadtreeCellKey {
+    let cells_per_degree = (1u64 << level) as Float;
+    let column = ((x + 180.0) * cells_per_degree).round() as i32;
+    let row = ((y + 90.0) * cells_per_degree).round() as i32;
+    (column, row, level)
+}
 
-            let mut intersected = Vec::new();
+/// The grid column/row of the cell at `level` that contains the point `(lng, lat)`.
+fn cell_key_for_point(lng: Float, lat: Float, level: u8) -> QuadtreeCellKey {
+    let cells_per_degree = (1u64 << level) as Float;
+    let column = ((lng + 180.0) * cells_per_degree).floor() as i32;
+    let row = ((lat + 90.0) * cells_per_degree).floor() as i32;
+    (column, row, level)
+}
 
-            for tz in timezones {
-                if tz.geometry.intersects(&rect) {
-                    intersected.push(tz.id as RoundInt);
-                }
-            }
+/// Recursively test and, if needed, split the cell `(x, y, x + size, y + size)`, inserting
+/// leaves (cells that weren't subdivided further) into `map`.
+#[allow(clippy::too_many_arguments)]
+fn build_quadtree_cell(timezones: &ConcreteTimezones, rtree: &RTree<ZoneBbox>, map: &CHashMap<QuadtreeCellKey, Vec<i16>>, x: Float, y: Float, size: Float, level: u8) {
+    let rect = Rect::new(Coord { x, y }, Coord { x: x + size, y: y + size });
+    let envelope = AABB::from_corners([x, y], [x + size, y + size]);
+
+    let intersected: Vec<i16> = rtree
+        .locate_in_envelope_intersecting(&envelope)
+        .filter(|candidate| timezones.get(candidate.id).is_some_and(|tz| tz.geometry.intersects(&rect)))
+        .map(|candidate| candidate.id as i16)
+        .collect();
+
+    if intersected.len() > TARGET_CELL_OCCUPANCY && level < MAX_QUADTREE_DEPTH {
+        let half = size / 2.0;
+        for (dx, dy) in [(0.0, 0.0), (half, 0.0), (0.0, half), (half, half)] {
+            build_quadtree_cell(timezones, rtree, map, x + dx, y + dy, half, level + 1);
+        }
+    } else {
+        map.insert(cell_origin_key(x, y, level), intersected);
+    }
+}
 
-            map.insert((x as RoundInt, y as RoundInt), intersected);
+/// Build the adaptive quadtree cache from the timezones.
+///
+/// Starts from the same 1° root grid as the old fixed cache, but any root (or descendant)
+/// cell whose intersecting-zone count exceeds [`TARGET_CELL_OCCUPANCY`] is recursively split
+/// into four equal sub-rects, down to [`MAX_QUADTREE_DEPTH`], instead of being left coarse
+/// near dense borders or overflowing a fixed-size array. Zone `bbox`es are indexed in an
+/// `rstar` R-tree first, so each cell only tests the few candidates whose bbox could
+/// intersect it, turning generation from `O(cells × zones)` into roughly `O(cells × log
+/// zones)`.
+pub fn get_cache_from_timezones(timezones: &ConcreteTimezones) -> HashMap<QuadtreeCellKey, Vec<i16>> {
+    let rtree = RTree::bulk_load(timezones.iter().map(|tz| ZoneBbox { id: tz.id, bbox: tz.bbox }).collect::<Vec<_>>());
+
+    let map = CHashMap::new();
+
+    (-180..180).into_par_iter().for_each(|x| {
+        for y in -90..90 {
+            build_quadtree_cell(timezones, &rtree, &map, x as Float, y as Float, 1.0, 0);
         }
     });
 
@@ -42,6 +116,15 @@ pub fn get_cache_from_timezones(timezones: &ConcreteTimezones) -> HashMap<RoundL
     cache
 }
 
+/// Look up the deepest populated quadtree leaf containing `(lng, lat)`.
+///
+/// Leaves live at whatever depth [`get_cache_from_timezones`] stopped subdividing at, so
+/// this walks from the 1° root downward, trying the cell that contains the point at each
+/// successive level, and returns the first one present in `cache`.
+pub fn lookup_quadtree_cache(cache: &HashMap<QuadtreeCellKey, Vec<i16>>, lng: Float, lat: Float) -> Option<&[i16]> {
+    (0..=MAX_QUADTREE_DEPTH).find_map(|level| cache.get(&cell_key_for_point(lng, lat, level)).map(Vec::as_slice))
+}
+
 /// Generate the bincode representation of the 100km cache.
 ///
 /// "100km" is a bit of a misnomer.  This is really 100km _at the equator_, but this
@@ -69,11 +152,88 @@ fn generate_timezone_bincode(geojson_features: FeatureCollection, bincode_destin
     std::fs::write(bincode_destination, bincode::serde::encode_to_vec(timezones, bincode::config::standard()).unwrap()).unwrap();
 }
 
-/// Generates new bincodes for the timezones and the cache from the GeoJSON.
+/// Generates new bincodes for the timezones, the cache, and the DST transition tables from
+/// the GeoJSON and a directory of raw IANA zoneinfo source files.
 #[cfg(feature = "self-contained")]
-pub fn generate_bincodes(geojson_features: FeatureCollection, timezone_bincode_destination: impl AsRef<Path>, cache_bincode_destination: impl AsRef<Path>) {
+pub fn generate_bincodes(
+    geojson_features: FeatureCollection,
+    timezone_bincode_destination: impl AsRef<Path>,
+    cache_bincode_destination: impl AsRef<Path>,
+    tzdata_dir: impl AsRef<Path>,
+    transitions_bincode_destination: impl AsRef<Path>,
+) {
     generate_timezone_bincode(geojson_features, timezone_bincode_destination.as_ref());
     generate_cache_bincode(timezone_bincode_destination, cache_bincode_destination);
+    generate_timezone_transitions_bincode(tzdata_dir, transitions_bincode_destination);
+}
+
+/// Generate the bincode representation of the per-zone DST transition tables.
+///
+/// `tzdata_dir` is a directory of raw IANA zoneinfo source files (e.g. a checkout of the
+/// `tz` database's `africa`, `europe`, `northamerica`, ... files), which are parsed the same
+/// way chrono-tz's build script does to produce, per zone name, an ordered list of
+/// [`TzTransition`]s.
+#[cfg(feature = "self-contained")]
+fn generate_timezone_transitions_bincode(tzdata_dir: impl AsRef<Path>, bincode_destination: impl AsRef<Path>) {
+    use parse_zoneinfo::{
+        line::{Line, LineParser},
+        table::TableBuilder,
+        transitions::TableTransitions,
+    };
+
+    let mut table_builder = TableBuilder::new();
+    let parser = LineParser::default();
+
+    for entry in std::fs::read_dir(tzdata_dir).unwrap() {
+        let path = entry.unwrap().path();
+        if !path.is_file() {
+            continue;
+        }
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        for line in contents.lines() {
+            match parser.parse_str(line) {
+                Ok(Line::Zone(zone)) => table_builder.add_zone_line(zone).unwrap(),
+                Ok(Line::Continuation(continuation)) => table_builder.add_continuation_line(continuation).unwrap(),
+                Ok(Line::Rule(rule)) => table_builder.add_rule_line(rule).unwrap(),
+                Ok(Line::Link(link)) => table_builder.add_link_line(link).unwrap(),
+                Ok(Line::Space) | Err(_) => {}
+            }
+        }
+    }
+
+    let table = table_builder.build();
+
+    let mut transitions: HashMap<String, Vec<TzTransition>> = HashMap::new();
+    for zone_name in table.zonesets.keys().chain(table.links.keys()) {
+        let Some(timespans) = table.timespans(zone_name) else {
+            continue;
+        };
+
+        let mut zone_transitions = vec![TzTransition {
+            timestamp: i64::MIN,
+            timespan: FixedTimespan {
+                utc_offset: timespans.first.utc_offset as i32,
+                dst_offset: timespans.first.dst_offset as i32,
+                abbreviation: timespans.first.name.clone(),
+            },
+        }];
+
+        for (timestamp, timespan) in &timespans.rest {
+            zone_transitions.push(TzTransition {
+                timestamp: *timestamp,
+                timespan: FixedTimespan {
+                    utc_offset: timespan.utc_offset as i32,
+                    dst_offset: timespan.dst_offset as i32,
+                    abbreviation: timespan.name.clone(),
+                },
+            });
+        }
+
+        transitions.insert(zone_name.clone(), zone_transitions);
+    }
+
+    std::fs::write(bincode_destination, bincode::serde::encode_to_vec(&transitions, bincode::config::standard()).unwrap()).unwrap();
 }
 
 /// Get the GeoJSON features from the binary assets.
@@ -95,22 +255,16 @@ pub static GEOJSON_ADDRESS: &str = "https://raw.githubusercontent.com/nvkelso/na
 pub static TIMEZONE_BINCODE_DESTINATION_NAME: &str = "ne_10m_time_zones.bincode";
 /// The name of the cache bincode file.
 pub static CACHE_BINCODE_DESTINATION_NAME: &str = "ne_time_zone_cache.bincode";
+/// The name of the timezone DST transition bincode file.
+pub static TIMEZONE_TRANSITIONS_BINCODE_DESTINATION_NAME: &str = "ne_time_zone_transitions.bincode";
 
 // Types.
 
-/// A rounded integer.
-pub type RoundInt = i16;
-/// A rounded longitude and latitude.
-pub type RoundLngLat = (RoundInt, RoundInt);
-//pub type LngLat = (f64, f64);
-
-/// This number is selected based on the existing data, and may need to be increased
-/// across dataset versions.  However, it is helpful to keep this as an array
-/// for cache locality in the map.
-const TIMEZONE_LIST_LENGTH: usize = 5;
-
 /// A collection of `id`s into the global time zone static cache.
-pub type NedTimezoneIds = [RoundInt; TIMEZONE_LIST_LENGTH];
+///
+/// Unlike the old fixed-size cache, a quadtree leaf can hold as many overlapping zones as
+/// actually intersect it, so this is a plain `Vec` rather than a fixed-length array.
+pub type NedTimezoneIds = Vec<i16>;
 /// A [`Timezone`] static reference.
 pub type NedTimezoneRef = &'static NedTimezone;
 /// A collection of [`Timezone`] static references.
@@ -227,20 +381,409 @@ impl From<(usize, &geojson::Feature)> for NedTimezone {
     }
 }
 
-// Helper methods.
+// DST offset resolution.
+
+/// A single UTC offset timespan within a zone's transition history, mirroring what
+/// chrono-tz's build step produces from the compiled zoneinfo data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixedTimespan {
+    /// The base UTC offset for this timespan, in seconds.
+    pub utc_offset: i32,
+    /// The additional DST offset applied during this timespan, in seconds.
+    pub dst_offset: i32,
+    /// The abbreviation in effect during this timespan (e.g. `PST`, `PDT`).
+    pub abbreviation: String,
+}
+
+impl FixedTimespan {
+    /// The total offset from UTC in effect during this timespan (`utc_offset + dst_offset`).
+    fn total_offset(&self) -> i32 {
+        self.utc_offset + self.dst_offset
+    }
+}
+
+/// A transition into a [`FixedTimespan`], keyed by the UTC instant (a Unix timestamp, in
+/// seconds) at which it takes effect.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TzTransition {
+    /// The UTC instant, in seconds since the Unix epoch, at which this timespan begins.
+    ///
+    /// The very first transition of a zone uses [`i64::MIN`] to represent "since the
+    /// beginning of time", i.e. the zone's initial (typically `LMT`) timespan.
+    pub timestamp: i64,
+    /// The timespan that is in effect from `timestamp` onward.
+    pub timespan: FixedTimespan,
+}
+
+#[cfg(feature = "self-contained")]
+static TIMEZONE_TRANSITIONS: OnceLock<HashMap<String, Vec<TzTransition>>> = OnceLock::new();
+
+/// Seed the per-zone DST transition tables from a decoded [`generate_timezone_transitions_bincode`]
+/// blob.
+///
+/// `rtz-core` has no `build.rs` of its own, so it can't embed this data itself via
+/// `include_bytes!(concat!(env!("OUT_DIR"), ...))` -- that only resolves for a crate with its
+/// own build script. A downstream crate's build script generates and embeds the bincode, then
+/// calls this once at startup so [`NedTimezone::offset_at`] has something to read. A later call
+/// is a no-op.
+#[cfg(feature = "self-contained")]
+pub fn init_timezone_transitions(bincode_bytes: &[u8]) {
+    let (map, _len): (HashMap<String, Vec<TzTransition>>, usize) = bincode::serde::decode_from_slice(bincode_bytes, bincode::config::standard()).unwrap();
+    let _ = TIMEZONE_TRANSITIONS.set(map);
+}
+
+/// Look up the transition history for an IANA zone `identifier`, if one is known.
+#[cfg(feature = "self-contained")]
+fn timezone_transitions(identifier: &str) -> Option<&'static [TzTransition]> {
+    TIMEZONE_TRANSITIONS.get()?.get(identifier).map(Vec::as_slice)
+}
+
+/// Look up the transition history for an IANA zone `identifier`, if one is known.
+#[cfg(not(feature = "self-contained"))]
+fn timezone_transitions(_identifier: &str) -> Option<&'static [TzTransition]> {
+    None
+}
+
+/// Binary search `transitions` for the offset in effect at `instant`, i.e. the timespan of
+/// the latest transition whose timestamp is `<= instant`.
+///
+/// Returns `None` when `transitions` is empty, so an identifier with no recorded history
+/// falls back the same way as one with no IANA match at all.
+fn resolve_offset_seconds(transitions: &[TzTransition], instant: i64) -> Option<i32> {
+    if transitions.is_empty() {
+        return None;
+    }
+
+    let index = transitions.partition_point(|transition| transition.timestamp <= instant);
+    let index = index.saturating_sub(1);
+
+    Some(transitions[index].timespan.total_offset())
+}
+
+impl NedTimezone {
+    /// Resolve the real UTC offset in effect at `datetime`, accounting for daylight saving
+    /// time, rather than the static [`raw_offset`](NedTimezone::raw_offset) pulled verbatim
+    /// from the GeoJSON.
+    ///
+    /// When [`identifier`](NedTimezone::identifier) names a zone with a known IANA
+    /// transition history, this binary-searches that history for the latest transition at
+    /// or before `datetime` and returns its `utc_offset + dst_offset`.  Instants before the
+    /// zone's first transition fall back to that first timespan.  Zones with no IANA match
+    /// (or built without the `self-contained` feature, or before
+    /// [`init_timezone_transitions`] has been called) fall back to `raw_offset`.
+    pub fn offset_at(&self, datetime: DateTime<Utc>) -> FixedOffset {
+        let offset_seconds = self
+            .identifier
+            .as_deref()
+            .and_then(timezone_transitions)
+            .and_then(|transitions| resolve_offset_seconds(transitions, datetime.timestamp()))
+            .unwrap_or(self.raw_offset);
+
+        FixedOffset::east_opt(offset_seconds).unwrap_or_else(|| FixedOffset::east_opt(self.raw_offset).expect("raw_offset should always be a valid FixedOffset"))
+    }
+
+    /// The canonical short BCP47 Unicode time zone ID for this zone (e.g. `uslax`), if one
+    /// is known for its [`identifier`](NedTimezone::identifier).
+    pub fn bcp47_id(&self) -> Option<&'static str> {
+        names::iana_to_bcp47(self.identifier.as_deref()?)
+    }
+
+    /// The localized display name of this zone under `locale` in the requested `style`
+    /// (e.g. "Pacific Time", "PST", or "Los Angeles"), per CLDR's `timeZoneNames` data.
+    ///
+    /// Returns `None` when `identifier` is unset, or when CLDR has no name for this zone in
+    /// `locale`.
+    pub fn display_name(&self, locale: &str, style: Style) -> Option<String> {
+        display_names::resolve(locale, self.identifier.as_deref()?, style)
+    }
+}
+
+// Spatial/temporal queries.
 
-/// Convert a [`Vec`] of [`i16`]s into [`NedTimezoneIds`].
-pub fn i16_vec_to_tomezoneids(value: Vec<i16>) -> NedTimezoneIds {
-    if value.len() > TIMEZONE_LIST_LENGTH {
-        panic!("Cannot convert a Vec<i16> with more than `TIMEZONE_LIST_LENGTH` elements into a TimezoneIds.");
+#[cfg(feature = "self-contained")]
+static NED_TIMEZONES: OnceLock<ConcreteTimezones> = OnceLock::new();
+
+/// Seed the dataset queried by [`timezones_intersecting_bbox`], [`timezones_intersecting_interval`],
+/// and [`timezones_intersecting_bbox_and_interval`] from a decoded [`generate_timezone_bincode`]
+/// blob.
+///
+/// See [`init_timezone_transitions`] for why this is a setter instead of an embedded
+/// `include_bytes!`. A later call is a no-op.
+#[cfg(feature = "self-contained")]
+pub fn init_ned_timezones(bincode_bytes: &[u8]) {
+    let (timezones, _len): (ConcreteTimezones, usize) = bincode::serde::decode_from_slice(bincode_bytes, bincode::config::standard()).unwrap();
+    let _ = NED_TIMEZONES.set(timezones);
+}
+
+#[cfg(feature = "self-contained")]
+fn ned_timezones() -> Option<&'static ConcreteTimezones> {
+    NED_TIMEZONES.get()
+}
+
+/// Find every zone whose geometry intersects `rect`.
+///
+/// Mirrors STAC's `bbox` item filter: each zone's precomputed [`bbox`](NedTimezone::bbox) is
+/// checked first as a cheap reject, and only candidates that pass are tested against the
+/// full `geometry` for precision. Returns an empty [`NedTimezoneRefs`] before
+/// [`init_ned_timezones`] has been called.
+#[cfg(feature = "self-contained")]
+pub fn timezones_intersecting_bbox(rect: Rect<Float>) -> NedTimezoneRefs {
+    let Some(timezones) = ned_timezones() else {
+        return Vec::new();
+    };
+
+    timezones.iter().filter(|tz| tz.bbox.intersects(&rect) && tz.geometry.intersects(&rect)).collect()
+}
+
+/// Find every zone whose geometry intersects `rect`.
+///
+/// Always returns an empty [`NedTimezoneRefs`]; built without the `self-contained` feature,
+/// which embeds the timezone data this query runs against.
+#[cfg(not(feature = "self-contained"))]
+pub fn timezones_intersecting_bbox(_rect: Rect<Float>) -> NedTimezoneRefs {
+    Vec::new()
+}
+
+/// Whether any of a zone's offset windows (the span between one transition and the next)
+/// overlaps `[start, end]`.
+///
+/// Only called from [`timezones_intersecting_interval`], which needs the `self-contained`
+/// feature's embedded transition tables; kept reachable without the feature so its
+/// edge cases (boundary transitions, empty history) have unit coverage.
+#[cfg_attr(not(feature = "self-contained"), allow(dead_code))]
+fn transitions_overlap_interval(transitions: &[TzTransition], start: i64, end: i64) -> bool {
+    transitions.iter().enumerate().any(|(index, transition)| {
+        let window_end = transitions.get(index + 1).map_or(i64::MAX, |next| next.timestamp);
+        transition.timestamp <= end && window_end > start
+    })
+}
+
+/// Find every zone whose UTC offset window overlaps `[start, end]`.
+///
+/// Mirrors STAC's `datetime` item filter.  A zone's offset can itself change within
+/// `[start, end]` (a DST transition), so this walks every transition window recorded for
+/// the zone's `identifier` and keeps the zone if any window overlaps the requested range.
+/// Zones with no IANA transition history (a static `raw_offset`) are always considered
+/// active, since they have no windows to overlap. Returns an empty [`NedTimezoneRefs`]
+/// before [`init_ned_timezones`] has been called.
+#[cfg(feature = "self-contained")]
+pub fn timezones_intersecting_interval(start: DateTime<Utc>, end: DateTime<Utc>) -> NedTimezoneRefs {
+    let Some(timezones) = ned_timezones() else {
+        return Vec::new();
+    };
+
+    let (start, end) = (start.timestamp(), end.timestamp());
+
+    timezones
+        .iter()
+        .filter(|tz| match tz.identifier.as_deref().and_then(timezone_transitions) {
+            Some(transitions) => transitions_overlap_interval(transitions, start, end),
+            None => true,
+        })
+        .collect()
+}
+
+/// Find every zone whose UTC offset window overlaps `[start, end]`.
+///
+/// Always returns an empty [`NedTimezoneRefs`]; built without the `self-contained` feature,
+/// which embeds the timezone data this query runs against.
+#[cfg(not(feature = "self-contained"))]
+pub fn timezones_intersecting_interval(_start: DateTime<Utc>, _end: DateTime<Utc>) -> NedTimezoneRefs {
+    Vec::new()
+}
+
+/// Find every zone in `rect` that is also active at some point within `[start, end]`.
+///
+/// The set-intersection of [`timezones_intersecting_bbox`] and
+/// [`timezones_intersecting_interval`], modeled after STAC's paired `intersects_bbox` /
+/// `intersects_datetime` item filters, combined into a single "zones in this box active
+/// over this time window" query.
+#[cfg(feature = "self-contained")]
+pub fn timezones_intersecting_bbox_and_interval(rect: Rect<Float>, start: DateTime<Utc>, end: DateTime<Utc>) -> NedTimezoneRefs {
+    let active_ids: std::collections::HashSet<usize> = timezones_intersecting_interval(start, end).into_iter().map(|tz| tz.id).collect();
+
+    timezones_intersecting_bbox(rect).into_iter().filter(|tz| active_ids.contains(&tz.id)).collect()
+}
+
+/// Find every zone in `rect` that is also active at some point within `[start, end]`.
+///
+/// Always returns an empty [`NedTimezoneRefs`]; built without the `self-contained` feature,
+/// which embeds the timezone data this query runs against.
+#[cfg(not(feature = "self-contained"))]
+pub fn timezones_intersecting_bbox_and_interval(_rect: Rect<Float>, _start: DateTime<Utc>, _end: DateTime<Utc>) -> NedTimezoneRefs {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "self-contained")]
+    use chrono::TimeZone;
+
+    fn timespan(utc_offset: i32, dst_offset: i32) -> FixedTimespan {
+        FixedTimespan { utc_offset, dst_offset, abbreviation: String::new() }
+    }
+
+    fn transitions() -> Vec<TzTransition> {
+        vec![
+            TzTransition { timestamp: i64::MIN, timespan: timespan(-8 * 3600, 0) },
+            TzTransition { timestamp: 1_615_705_200, timespan: timespan(-8 * 3600, 3600) },
+            TzTransition { timestamp: 1_636_264_800, timespan: timespan(-8 * 3600, 0) },
+        ]
+    }
+
+    #[test]
+    fn resolve_offset_seconds_before_first_transition_uses_first_timespan() {
+        assert_eq!(resolve_offset_seconds(&transitions(), 0), Some(-8 * 3600));
+    }
+
+    #[test]
+    fn resolve_offset_seconds_exactly_on_a_transition_uses_the_new_timespan() {
+        assert_eq!(resolve_offset_seconds(&transitions(), 1_615_705_200), Some(-7 * 3600));
     }
 
-    [
-        #[allow(clippy::get_first)]
-        value.get(0).cloned().unwrap_or(-1),
-        value.get(1).cloned().unwrap_or(-1),
-        value.get(2).cloned().unwrap_or(-1),
-        value.get(3).cloned().unwrap_or(-1),
-        value.get(4).cloned().unwrap_or(-1),
-    ]
-}
\ No newline at end of file
+    #[test]
+    fn resolve_offset_seconds_between_transitions_uses_the_latest_one() {
+        assert_eq!(resolve_offset_seconds(&transitions(), 1_620_000_000), Some(-7 * 3600));
+        assert_eq!(resolve_offset_seconds(&transitions(), 1_700_000_000), Some(-8 * 3600));
+    }
+
+    #[test]
+    fn resolve_offset_seconds_on_an_empty_history_returns_none() {
+        assert_eq!(resolve_offset_seconds(&[], 0), None);
+    }
+
+    #[test]
+    fn transitions_overlap_interval_true_for_range_entirely_within_one_window() {
+        assert!(transitions_overlap_interval(&transitions(), 1_620_000_000, 1_625_000_000));
+    }
+
+    #[test]
+    fn transitions_overlap_interval_true_for_range_spanning_a_transition_boundary() {
+        assert!(transitions_overlap_interval(&transitions(), 1_615_000_000, 1_616_000_000));
+    }
+
+    #[test]
+    fn transitions_overlap_interval_false_when_there_are_no_windows() {
+        assert!(!transitions_overlap_interval(&[], 0, 100));
+    }
+
+    fn rect(x0: Float, y0: Float, x1: Float, y1: Float) -> Rect<Float> {
+        Rect::new(Coord { x: x0, y: y0 }, Coord { x: x1, y: y1 })
+    }
+
+    fn make_zone(id: usize, bbox: Rect<Float>) -> NedTimezone {
+        NedTimezone {
+            id,
+            identifier: None,
+            description: String::new(),
+            dst_description: None,
+            offset: String::new(),
+            zone: 0.0,
+            raw_offset: 0,
+            bbox,
+            geometry: Geometry::Rect(bbox),
+        }
+    }
+
+    #[test]
+    fn cell_key_for_point_matches_cell_origin_key_at_the_cell_corner() {
+        assert_eq!(cell_key_for_point(12.0, 34.0, 0), cell_origin_key(12.0, 34.0, 0));
+        assert_eq!(cell_key_for_point(12.25, 34.25, 2), cell_origin_key(12.25, 34.25, 2));
+    }
+
+    #[test]
+    fn quadtree_cache_subdivides_a_crowded_cell_and_lookup_still_finds_every_zone_at_the_point() {
+        // Seven zones all contain the origin -- more than TARGET_CELL_OCCUPANCY -- so the root
+        // cell covering it must be recursively split.
+        let zones = ConcreteTimezones(vec![
+            make_zone(0, rect(-180.0, -90.0, 180.0, 90.0)),
+            make_zone(1, rect(-10.0, -10.0, 10.0, 10.0)),
+            make_zone(2, rect(-1.0, -1.0, 1.0, 1.0)),
+            make_zone(3, rect(-0.5, -0.5, 0.5, 0.5)),
+            make_zone(4, rect(-0.25, -0.25, 0.25, 0.25)),
+            make_zone(5, rect(-0.1, -0.1, 0.1, 0.1)),
+            make_zone(6, rect(-0.05, -0.05, 0.05, 0.05)),
+            // Lives far away, so it must never show up in the origin's leaf.
+            make_zone(7, rect(170.0, 80.0, 180.0, 90.0)),
+        ]);
+
+        let cache = get_cache_from_timezones(&zones);
+
+        let (_, _, level) = (0..=MAX_QUADTREE_DEPTH)
+            .map(|level| cell_key_for_point(0.0, 0.0, level))
+            .find(|key| cache.contains_key(key))
+            .expect("some leaf should cover the origin");
+        assert!(level > 0, "the crowded root cell should have been subdivided");
+
+        let ids = lookup_quadtree_cache(&cache, 0.0, 0.0).expect("a populated leaf at the origin");
+
+        for zone in zones.iter() {
+            let should_be_present = zone.geometry.intersects(&Coord { x: 0.0, y: 0.0 });
+            assert_eq!(ids.contains(&(zone.id as i16)), should_be_present, "zone {} at the origin", zone.id);
+        }
+    }
+
+    #[test]
+    fn quadtree_cache_lookup_is_empty_far_from_every_zone() {
+        let zones = ConcreteTimezones(vec![make_zone(0, rect(-1.0, -1.0, 1.0, 1.0))]);
+
+        let cache = get_cache_from_timezones(&zones);
+
+        assert_eq!(lookup_quadtree_cache(&cache, 150.0, -60.0), Some([].as_slice()));
+    }
+
+    /// Exercises the whole `self-contained` path a downstream `build.rs` is responsible for:
+    /// a [`TzTransition`] table encoded to bincode (as
+    /// [`generate_timezone_transitions_bincode`] would write it), handed to
+    /// [`init_timezone_transitions`] (as a downstream crate's `include_bytes!` would), and
+    /// then read back through [`NedTimezone::offset_at`].
+    #[test]
+    #[cfg(feature = "self-contained")]
+    fn generated_transitions_bincode_round_trips_through_init_and_offset_at() {
+        let mut zone = make_zone(0, rect(-125.0, 32.0, -114.0, 42.0));
+        zone.identifier = Some("America/Los_Angeles".to_owned());
+        zone.raw_offset = -8 * 3600;
+
+        let mut transitions_by_identifier = HashMap::new();
+        transitions_by_identifier.insert("America/Los_Angeles".to_owned(), transitions());
+        let transitions_bytes = bincode::serde::encode_to_vec(&transitions_by_identifier, bincode::config::standard()).unwrap();
+        init_timezone_transitions(&transitions_bytes);
+
+        assert_eq!(zone.offset_at(Utc.timestamp_opt(1_620_000_000, 0).unwrap()).local_minus_utc(), -7 * 3600);
+        assert_eq!(zone.offset_at(Utc.timestamp_opt(1_700_000_000, 0).unwrap()).local_minus_utc(), -8 * 3600);
+    }
+
+    /// Exercises the whole `self-contained` path a downstream `build.rs` is responsible for:
+    /// a [`ConcreteTimezones`] dataset encoded to bincode (as [`generate_timezone_bincode`]
+    /// would write it), handed to [`init_ned_timezones`] (as a downstream crate's
+    /// `include_bytes!` would), and then read back through the `timezones_intersecting_*`
+    /// queries.
+    #[test]
+    #[cfg(feature = "self-contained")]
+    fn generated_ned_timezones_bincode_round_trips_through_init_and_queries() {
+        let mut zone = make_zone(0, rect(-125.0, 32.0, -114.0, 42.0));
+        zone.identifier = Some("America/Los_Angeles".to_owned());
+
+        let timezones = ConcreteTimezones(vec![zone]);
+        let timezones_bytes = bincode::serde::encode_to_vec(&timezones, bincode::config::standard()).unwrap();
+        init_ned_timezones(&timezones_bytes);
+
+        let mut transitions_by_identifier = HashMap::new();
+        transitions_by_identifier.insert("America/Los_Angeles".to_owned(), transitions());
+        let transitions_bytes = bincode::serde::encode_to_vec(&transitions_by_identifier, bincode::config::standard()).unwrap();
+        init_timezone_transitions(&transitions_bytes);
+
+        let inside = rect(-120.0, 35.0, -118.0, 37.0);
+        assert_eq!(timezones_intersecting_bbox(inside).len(), 1);
+
+        let far_away = rect(100.0, 0.0, 101.0, 1.0);
+        assert!(timezones_intersecting_bbox(far_away).is_empty());
+
+        let during_dst = (Utc.timestamp_opt(1_620_000_000, 0).unwrap(), Utc.timestamp_opt(1_625_000_000, 0).unwrap());
+        assert_eq!(timezones_intersecting_interval(during_dst.0, during_dst.1).len(), 1);
+        assert_eq!(timezones_intersecting_bbox_and_interval(inside, during_dst.0, during_dst.1).len(), 1);
+        assert!(timezones_intersecting_bbox_and_interval(far_away, during_dst.0, during_dst.1).is_empty());
+    }
+}
+
@@ -0,0 +1,4 @@
+//! Core types and build-time generation shared by `rtz`'s timezone lookups.
+
+pub mod base;
+pub mod geo;